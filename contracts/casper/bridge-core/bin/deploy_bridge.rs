@@ -19,6 +19,7 @@ fn main() {
         admin: deployer,
         fee_receiver: deployer,
         fee_bps: 30, // example: 0.30%
+        chain_id: 1, // Casper id in the relayer's chain registry
     };
 
     // Optional: set explicit gas for deploy (pattern from docs)
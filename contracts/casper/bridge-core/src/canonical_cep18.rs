@@ -1,13 +1,19 @@
 use odra::prelude::*;
-use odra::casper_types::U256;
+use odra::casper_types::bytesrepr::Bytes;
+use odra::casper_types::{PublicKey, U256};
 use odra_modules::access::Ownable;
 use odra_modules::cep18_token::Cep18;
 
+use crate::permit::Permit;
+use crate::token_receiver::TokenReceiverContractRef;
+use odra::ContractRef;
+
 /// Canonical CEP-18 token (used on SOURCE chain).
 ///
 /// - Standard CEP-18 behavior
 /// - Initial supply minted to deployer
 /// - Owner can mint more tokens
+/// - Holders can approve with an off-chain signature (`permit`)
 #[odra::module]
 pub struct CanonicalToken {
     /// CEP-18 implementation
@@ -15,6 +21,9 @@ pub struct CanonicalToken {
 
     /// Owner (deployer) for admin actions
     ownable: SubModule<Ownable>,
+
+    /// Nonces for signed `permit` approvals
+    permit: SubModule<Permit>,
 }
 
 #[odra::module]
@@ -60,6 +69,59 @@ impl CanonicalToken {
         self.token.raw_burn(owner, amount);
     }
 
+    // -------- Permit (signed approvals) --------
+
+    /// Set `spender`'s allowance over the signer's tokens to `value` from
+    /// an off-chain signature, so holders can skip a separate `approve`.
+    ///
+    /// `signature` is the owner's signature over `compute_permit_hash`;
+    /// the owner is the account of `public_key`.
+    pub fn permit(
+        &mut self,
+        public_key: PublicKey,
+        spender: Address,
+        value: U256,
+        nonce: u64,
+        deadline: u64,
+        signature: Bytes,
+    ) {
+        let owner = self
+            .permit
+            .consume(&public_key, &spender, &value, nonce, deadline, &signature);
+        self.token.raw_approve(&owner, &spender, &value);
+    }
+
+    /// Nonce the next permit signed by `owner` must carry.
+    pub fn permit_nonce(&self, owner: Address) -> u64 {
+        self.permit.nonce_of(owner)
+    }
+
+    /// Recomputes the message an owner signs for `permit`.
+    pub fn compute_permit_hash(
+        &self,
+        public_key: PublicKey,
+        spender: Address,
+        value: U256,
+        nonce: u64,
+        deadline: u64,
+    ) -> [u8; 32] {
+        self.permit.hash(&public_key, &spender, &value, nonce, deadline)
+    }
+
+    /// Transfer `amount` to `recipient` and, if it is a contract, call its
+    /// `on_token_received(sender, amount, data)` in the same deploy.
+    ///
+    /// Sending to `BridgeCore` with `data = (destination_chain, recipient)`
+    /// bridges the tokens without a prior `approve`.
+    pub fn transfer_and_call(&mut self, recipient: Address, amount: U256, data: Bytes) {
+        let sender = self.env().caller();
+        self.token.transfer(&recipient, &amount);
+        if recipient.is_contract() {
+            TokenReceiverContractRef::new(self.env(), recipient)
+                .on_token_received(sender, amount, data);
+        }
+    }
+
     // -------- CEP-18 standard API --------
 
     delegate! {
@@ -1,8 +1,13 @@
 #![cfg_attr(not(test), no_std)]
 #![cfg_attr(not(test), no_main)]
+#![recursion_limit = "256"]
 extern crate alloc;
 
 pub mod flipper;
 pub mod bridge_core;
+pub mod timelock;
+pub mod permit;
+pub mod token_receiver;
 pub mod wrapped_cep18;
 pub mod canonical_cep18;
+pub mod wrapped_cspr;
@@ -0,0 +1,80 @@
+use odra::prelude::*;
+use odra::casper_types::bytesrepr::{Bytes, ToBytes};
+use odra::casper_types::{PublicKey, U256};
+
+#[odra::odra_error]
+pub enum PermitError {
+    PermitExpired = 12_000,
+    InvalidPermitNonce = 12_001,
+    InvalidPermitSignature = 12_002,
+}
+
+/// Off-chain signed approvals for the bridge's CEP-18 tokens.
+///
+/// Only verifies permits and tracks per-owner nonces; the owning token
+/// applies the allowance.
+#[odra::module(errors = PermitError)]
+pub struct Permit {
+    // owner -> nonce the next permit must carry
+    nonces: Mapping<Address, u64>,
+}
+
+#[odra::module]
+impl Permit {
+    /// Nonce the next permit signed by `owner` must carry.
+    pub fn nonce_of(&self, owner: Address) -> u64 {
+        self.nonces.get_or_default(&owner)
+    }
+}
+
+impl Permit {
+    /// Check a permit signed by `public_key`, consume its nonce and return
+    /// the owner (the account of `public_key`).
+    pub fn consume(
+        &mut self,
+        public_key: &PublicKey,
+        spender: &Address,
+        value: &U256,
+        nonce: u64,
+        deadline: u64,
+        signature: &Bytes,
+    ) -> Address {
+        if self.env().get_block_time() > deadline {
+            self.env().revert(PermitError::PermitExpired);
+        }
+        let owner = Address::from(public_key.clone());
+        if nonce != self.nonce_of(owner) {
+            self.env().revert(PermitError::InvalidPermitNonce);
+        }
+        let message = Bytes::from(self.hash(public_key, spender, value, nonce, deadline).to_vec());
+        if !self.env().verify_signature(&message, signature, public_key) {
+            self.env().revert(PermitError::InvalidPermitSignature);
+        }
+
+        self.nonces.set(&owner, nonce + 1);
+        owner
+    }
+
+    /// Message an owner signs to approve `spender` for `value`.
+    ///
+    /// blake2b-256 over the concatenated bytesrepr (little-endian) encodings of
+    /// `token: Address | public_key: PublicKey | spender: Address |
+    /// value: U256 | nonce: u64 | deadline: u64` (`deadline` is block time, ms).
+    pub fn hash(
+        &self,
+        public_key: &PublicKey,
+        spender: &Address,
+        value: &U256,
+        nonce: u64,
+        deadline: u64,
+    ) -> [u8; 32] {
+        let mut data = Vec::new();
+        data.extend(self.env().self_address().to_bytes().unwrap_or_revert(self));
+        data.extend(public_key.to_bytes().unwrap_or_revert(self));
+        data.extend(spender.to_bytes().unwrap_or_revert(self));
+        data.extend(value.to_bytes().unwrap_or_revert(self));
+        data.extend(nonce.to_le_bytes());
+        data.extend(deadline.to_le_bytes());
+        self.env().hash(data)
+    }
+}
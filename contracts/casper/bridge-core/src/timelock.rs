@@ -0,0 +1,194 @@
+use odra::prelude::*;
+use odra::casper_types::bytesrepr::{Bytes, ToBytes};
+use odra::casper_types::PublicKey;
+
+use crate::bridge_core::{FeeSchedule, TokenConfig};
+
+/// Sensitive BridgeCore change that must wait out the timelock delay.
+#[odra::odra_type]
+pub enum GovernanceAction {
+    SetFeeBps(u32),
+    SetFeeReceiver(Address),
+    SetTokenConfig(Address, TokenConfig),
+    GrantRelayer(Address),
+    SetTimelockDelay(u64),
+    // (token, route or None for the token-wide schedule, schedule)
+    SetTokenFee(Address, Option<u32>, FeeSchedule),
+    ClearTokenFee(Address, Option<u32>),
+    SetInboundFee(Address, FeeSchedule),
+    SetRelayerThreshold(u32),
+    AddValidator(PublicKey),
+    // (old key, new key)
+    RotateValidator(PublicKey, PublicKey),
+    SetValidatorThreshold(u32),
+    SetReleaseDelay(u64),
+    RemoveValidator(PublicKey),
+}
+
+/// Lifecycle of a scheduled operation.
+#[odra::odra_type]
+pub enum OperationStatus {
+    Pending = 0,
+    Executed = 1,
+    Cancelled = 2,
+}
+
+/// Scheduled governance action, executable from `ready_at` (block time, ms).
+#[odra::odra_type]
+pub struct Operation {
+    pub action: GovernanceAction,
+    pub ready_at: u64,
+    pub status: OperationStatus,
+}
+
+/// Longest accepted delay (30 days, ms), so a raised delay can still be
+/// lowered again through a `SetTimelockDelay` operation.
+pub const MAX_TIMELOCK_DELAY: u64 = 30 * 24 * 60 * 60 * 1000;
+
+#[odra::odra_error]
+pub enum TimelockError {
+    OperationNotFound = 11_000,
+    OperationNotPending = 11_001,
+    OperationNotReady = 11_002,
+    DelayTooLong = 11_003,
+    ReadyAtOverflow = 11_004,
+}
+
+#[odra::event]
+pub struct OperationScheduled {
+    pub id: [u8; 32],
+    // bytesrepr-serialized `GovernanceAction` (events cannot hold data enums)
+    pub action: Bytes,
+    pub ready_at: u64,
+    pub scheduled_by: Address,
+}
+
+#[odra::event]
+pub struct OperationExecuted {
+    pub id: [u8; 32],
+    pub executed_by: Address,
+}
+
+#[odra::event]
+pub struct OperationCancelled {
+    pub id: [u8; 32],
+    pub cancelled_by: Address,
+}
+
+#[odra::event]
+pub struct TimelockDelayUpdated {
+    pub old_delay: u64,
+    pub new_delay: u64,
+}
+
+/// Timelock for BridgeCore governance.
+///
+/// Only stores and sequences operations; who may schedule, execute or
+/// cancel is decided by the owning contract.
+#[odra::module(
+    events = [OperationScheduled, OperationExecuted, OperationCancelled, TimelockDelayUpdated],
+    errors = TimelockError
+)]
+pub struct Timelock {
+    // minimum wait between scheduling and execution, in ms (0 = disabled)
+    delay: Var<u64>,
+    operations: Mapping<[u8; 32], Operation>,
+    // mixed into operation ids so identical actions get distinct ids
+    nonce: Var<u64>,
+}
+
+#[odra::module]
+impl Timelock {
+    /// Current delay in milliseconds.
+    pub fn get_delay(&self) -> u64 {
+        self.delay.get_or_default()
+    }
+
+    /// Operation stored under `id`, if any.
+    pub fn get_operation(&self, id: [u8; 32]) -> Option<Operation> {
+        self.operations.get(&id)
+    }
+}
+
+impl Timelock {
+    pub fn set_delay(&mut self, new_delay: u64) {
+        if new_delay > MAX_TIMELOCK_DELAY {
+            self.env().revert(TimelockError::DelayTooLong);
+        }
+        let old_delay = self.delay.get_or_default();
+        self.delay.set(new_delay);
+
+        self.env().emit_event(TimelockDelayUpdated {
+            old_delay,
+            new_delay,
+        });
+    }
+
+    /// Store `action` and return its operation id.
+    pub fn schedule(&mut self, action: GovernanceAction) -> [u8; 32] {
+        let nonce = self.nonce.get_or_default() + 1;
+        self.nonce.set(nonce);
+
+        let action_bytes = action.to_bytes().unwrap_or_revert(self);
+        let mut data = action_bytes.clone();
+        data.extend(nonce.to_le_bytes());
+        let id = self.env().hash(data);
+
+        let ready_at = self
+            .env()
+            .get_block_time()
+            .checked_add(self.get_delay())
+            .unwrap_or_else(|| self.env().revert(TimelockError::ReadyAtOverflow));
+        self.operations.set(&id, Operation {
+            action,
+            ready_at,
+            status: OperationStatus::Pending,
+        });
+
+        self.env().emit_event(OperationScheduled {
+            id,
+            action: Bytes::from(action_bytes),
+            ready_at,
+            scheduled_by: self.env().caller(),
+        });
+        id
+    }
+
+    /// Mark a ready operation executed and hand back its action.
+    pub fn take_ready(&mut self, id: &[u8; 32]) -> GovernanceAction {
+        let mut operation = self.get_pending_or_revert(id);
+        if self.env().get_block_time() < operation.ready_at {
+            self.env().revert(TimelockError::OperationNotReady);
+        }
+        operation.status = OperationStatus::Executed;
+        self.operations.set(id, operation.clone());
+
+        self.env().emit_event(OperationExecuted {
+            id: *id,
+            executed_by: self.env().caller(),
+        });
+        operation.action
+    }
+
+    pub fn cancel(&mut self, id: &[u8; 32]) {
+        let mut operation = self.get_pending_or_revert(id);
+        operation.status = OperationStatus::Cancelled;
+        self.operations.set(id, operation);
+
+        self.env().emit_event(OperationCancelled {
+            id: *id,
+            cancelled_by: self.env().caller(),
+        });
+    }
+
+    fn get_pending_or_revert(&self, id: &[u8; 32]) -> Operation {
+        let operation = self
+            .operations
+            .get(id)
+            .unwrap_or_else(|| self.env().revert(TimelockError::OperationNotFound));
+        if operation.status != OperationStatus::Pending {
+            self.env().revert(TimelockError::OperationNotPending);
+        }
+        operation
+    }
+}
@@ -0,0 +1,11 @@
+use odra::prelude::*;
+use odra::casper_types::bytesrepr::Bytes;
+use odra::casper_types::U256;
+
+/// Contract notified by `transfer_and_call` on the bridge's CEP-18 tokens
+/// (implemented by `BridgeCore`).
+#[odra::external_contract]
+pub trait TokenReceiver {
+    /// Called by the token after `amount` moved from `sender` to the receiver.
+    fn on_token_received(&mut self, sender: Address, amount: U256, data: Bytes);
+}
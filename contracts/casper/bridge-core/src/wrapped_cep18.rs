@@ -1,18 +1,26 @@
 use odra::prelude::*;
-use odra::casper_types::U256;
+use odra::casper_types::bytesrepr::Bytes;
+use odra::casper_types::{PublicKey, U256};
 use odra_modules::access::Ownable;
 use odra_modules::cep18_token::Cep18;
 
+use crate::permit::Permit;
+use crate::token_receiver::TokenReceiverContractRef;
+use odra::ContractRef;
+
 /// Custom errors for the wrapped token.
 #[odra::odra_error]
 pub enum WrappedTokenError {
     BridgeNotSet = 1,
     CallerNotBridge = 2,
+    MaxSupplyExceeded = 3,
 }
 
 /// Wrapped CEP-18 token used by BridgeX.
 /// - Uses odra_modules::cep18_token::Cep18 under the hood (full CEP-18).
 /// - Only the configured `bridge_core` address can mint/burn.
+/// - Optional owner-set `max_supply` caps what the bridge can ever mint.
+/// - Holders can approve with an off-chain signature (`permit`).
 #[odra::module(errors = WrappedTokenError)]
 pub struct WrappedToken {
     /// Internal CEP-18 implementation.
@@ -23,6 +31,12 @@ pub struct WrappedToken {
 
     /// The only address allowed to mint/burn: your BridgeCore contract.
     bridge_core: Var<Address>,
+
+    /// Maximum total supply (unset or 0 = uncapped).
+    max_supply: Var<U256>,
+
+    /// Nonces for signed `permit` approvals.
+    permit: SubModule<Permit>,
 }
 
 #[odra::module]
@@ -73,6 +87,9 @@ impl WrappedToken {
     /// Callable only by BridgeCore.
     pub fn mint_for_bridge(&mut self, recipient: &Address, amount: &U256) {
         self.assert_bridge();
+        if *amount > self.mintable_headroom() {
+            self.env().revert(WrappedTokenError::MaxSupplyExceeded);
+        }
         self.token.raw_mint(recipient, amount);
     }
 
@@ -84,6 +101,14 @@ impl WrappedToken {
         self.token.raw_burn(owner, amount);
     }
 
+    /// Move wrapped tokens without an allowance (bridge and submitter fees).
+    ///
+    /// Callable only by BridgeCore.
+    pub fn transfer_for_bridge(&mut self, owner: &Address, recipient: &Address, amount: &U256) {
+        self.assert_bridge();
+        self.token.raw_transfer(owner, recipient, amount);
+    }
+
     // -------- Admin entrypoints (for you / governance) --------
 
     /// Change which contract is considered the bridge.
@@ -95,11 +120,88 @@ impl WrappedToken {
         self.bridge_core.set(*new_bridge);
     }
 
+    /// Cap the total supply the bridge can mint (0 removes the cap).
+    ///
+    /// Callable only by the module owner. A cap below the current supply
+    /// is allowed and simply blocks further mints.
+    pub fn set_max_supply(&mut self, max_supply: &U256) {
+        let caller = self.env().caller();
+        self.ownable.assert_owner(&caller);
+        self.max_supply.set(*max_supply);
+    }
+
+    /// Read the configured max supply (0 = uncapped).
+    pub fn get_max_supply(&self) -> U256 {
+        self.max_supply.get_or_default()
+    }
+
+    /// How much more can be minted before hitting `max_supply`.
+    pub fn mintable_headroom(&self) -> U256 {
+        let max_supply = self.max_supply.get_or_default();
+        if max_supply.is_zero() {
+            return U256::MAX;
+        }
+        max_supply.saturating_sub(self.token.total_supply())
+    }
+
     /// Read the current bridge_core address.
     pub fn get_bridge_core(&self) -> Address {
         self.bridge_address()
     }
 
+    // -------- Permit (signed approvals) --------
+
+    /// Set `spender`'s allowance over the signer's tokens to `value` from
+    /// an off-chain signature, so holders can skip a separate `approve`.
+    ///
+    /// `signature` is the owner's signature over `compute_permit_hash`;
+    /// the owner is the account of `public_key`.
+    pub fn permit(
+        &mut self,
+        public_key: PublicKey,
+        spender: Address,
+        value: U256,
+        nonce: u64,
+        deadline: u64,
+        signature: Bytes,
+    ) {
+        let owner = self
+            .permit
+            .consume(&public_key, &spender, &value, nonce, deadline, &signature);
+        self.token.raw_approve(&owner, &spender, &value);
+    }
+
+    /// Nonce the next permit signed by `owner` must carry.
+    pub fn permit_nonce(&self, owner: Address) -> u64 {
+        self.permit.nonce_of(owner)
+    }
+
+    /// Recomputes the message an owner signs for `permit`.
+    pub fn compute_permit_hash(
+        &self,
+        public_key: PublicKey,
+        spender: Address,
+        value: U256,
+        nonce: u64,
+        deadline: u64,
+    ) -> [u8; 32] {
+        self.permit.hash(&public_key, &spender, &value, nonce, deadline)
+    }
+
+    /// Transfer `amount` to `recipient` and, if it is a contract, call its
+    /// `on_token_received(sender, amount, data)` in the same deploy.
+    ///
+    /// Sending to `BridgeCore` with `data = (destination_chain, recipient)`
+    /// bridges the tokens without a prior `approve`.
+    pub fn transfer_and_call(&mut self, recipient: Address, amount: U256, data: Bytes) {
+        let sender = self.env().caller();
+        self.token.transfer(&recipient, &amount);
+        if recipient.is_contract() {
+            TokenReceiverContractRef::new(self.env(), recipient)
+                .on_token_received(sender, amount, data);
+        }
+    }
+
     // -------- User-facing CEP-18 API (delegated) --------
     //
     // These are normal token functions that wallets/dApps will call.
@@ -0,0 +1,105 @@
+use odra::prelude::*;
+use odra::casper_types::U256;
+use odra::uints::{ToU256, ToU512};
+use odra_modules::cep18_token::Cep18;
+
+/// Custom errors for the wrapped CSPR token.
+#[odra::odra_error]
+pub enum WrappedCsprError {
+    ZeroAmount = 1,
+    CannotWithdrawToContract = 2,
+}
+
+#[odra::event]
+pub struct Deposited {
+    pub account: Address,
+    pub amount: U256,
+}
+
+#[odra::event]
+pub struct Withdrawn {
+    pub account: Address,
+    pub amount: U256,
+}
+
+/// Wrapped CSPR (WCSPR) token.
+///
+/// - Full CEP-18 backed 1:1 by the CSPR held in the contract purse.
+/// - `deposit` mints for the attached CSPR, `withdraw` burns and pays it back.
+/// - There is no other mint/burn path, so total supply always equals the
+///   purse balance and BridgeCore can lock WCSPR as a plain canonical token.
+#[odra::module(events = [Deposited, Withdrawn], errors = WrappedCsprError)]
+pub struct WrappedCspr {
+    /// Internal CEP-18 implementation.
+    token: SubModule<Cep18>,
+}
+
+#[odra::module]
+impl WrappedCspr {
+    /// Initialize with fixed WCSPR metadata (9 decimals, like CSPR motes).
+    pub fn init(&mut self) {
+        self.token.init(
+            "WCSPR".to_string(),
+            "Wrapped CSPR".to_string(),
+            9,
+            U256::zero(),
+        );
+    }
+
+    /// Wrap the attached CSPR into the same amount of WCSPR for the caller.
+    #[odra(payable)]
+    pub fn deposit(&mut self) {
+        let caller = self.env().caller();
+        let amount = self.env().attached_value().to_u256().unwrap_or_revert(self);
+        if amount.is_zero() {
+            self.env().revert(WrappedCsprError::ZeroAmount);
+        }
+
+        self.token.raw_mint(&caller, &amount);
+
+        self.env().emit_event(Deposited {
+            account: caller,
+            amount,
+        });
+    }
+
+    /// Burn `amount` WCSPR from the caller and send back the same CSPR.
+    ///
+    /// Only accounts can withdraw: native CSPR cannot be pushed to a
+    /// contract purse, so contracts must transfer WCSPR to an account first.
+    pub fn withdraw(&mut self, amount: &U256) {
+        let caller = self.env().caller();
+        if amount.is_zero() {
+            self.env().revert(WrappedCsprError::ZeroAmount);
+        }
+        if caller.is_contract() {
+            self.env().revert(WrappedCsprError::CannotWithdrawToContract);
+        }
+
+        self.token.raw_burn(&caller, amount);
+        self.env().transfer_tokens(&caller, &amount.to_u512());
+
+        self.env().emit_event(Withdrawn {
+            account: caller,
+            amount: *amount,
+        });
+    }
+
+    // -------- CEP-18 standard API --------
+
+    delegate! {
+        to self.token {
+            fn name(&self) -> String;
+            fn symbol(&self) -> String;
+            fn decimals(&self) -> u8;
+            fn total_supply(&self) -> U256;
+            fn balance_of(&self, address: &Address) -> U256;
+            fn allowance(&self, owner: &Address, spender: &Address) -> U256;
+            fn approve(&mut self, spender: &Address, amount: &U256);
+            fn decrease_allowance(&mut self, spender: &Address, decr_by: &U256);
+            fn increase_allowance(&mut self, spender: &Address, inc_by: &U256);
+            fn transfer(&mut self, recipient: &Address, amount: &U256);
+            fn transfer_from(&mut self, owner: &Address, recipient: &Address, amount: &U256);
+        }
+    }
+}
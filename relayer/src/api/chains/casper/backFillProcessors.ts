@@ -5,7 +5,6 @@ import { logger } from '../../lib/utils/logger';
 import { persistCasperLockedCanonicalEvent } from './persistLockedEvent';
 import { updateCasperNetworkStatus } from './networkStatus';
 import { enqueueLockedCanonical } from '../../lib/utils/jobs/queue/enqueue';
-import { generateEventId } from '../../lib/utils/eventId';
 
 const CASPER_CHAIN_KEY = 'casper:1';
 
@@ -35,18 +34,7 @@ export async function runCasperBackfillOnce() {
   // 3) Persist + enqueue jobs (idempotent)
   for (const ev of events) {
     try {
-      await persistCasperLockedCanonicalEvent(ev);
-
-      const eventId = generateEventId({
-        sourceChain: ev.sourceChain,
-        txHash: ev.txHash,
-        logIndex: ev.logIndex,
-        token: ev.token,
-        amount: ev.amount,
-        nonce: ev.nonce,
-        destChainId: ev.destChainId,
-        destAddress: ev.destAddress,
-      });
+      const eventId = await persistCasperLockedCanonicalEvent(ev);
 
       await enqueueLockedCanonical(eventId);
     } catch (err) {
@@ -11,7 +11,6 @@ import { normalizeCasperMintedWrapped } from './normalizers/normalizeMintedWrapp
 import { normalizeCasperUnlockedCanonical } from './normalizers/normalizeUnlockedCanonical';
 import { loadCasperConfig } from './config';
 import { updateCasperNetworkStatus } from './networkStatus';
-import { generateEventIdCore } from '../../lib/utils/crossEventId';
 import {
   enqueueCasperBurnedWrapped,
   enqueueCasperLockedCanonical,
@@ -94,20 +93,16 @@ export function startCasperListener() {
         case 'LockedCanonical': {
           const ev = normalizeCasperLockedCanonical(msg);
 
-          if (!ev.nonce || !ev.destChainId || !ev.destAddress) {
+          if (
+            !ev.nonce ||
+            !ev.destChainId ||
+            !ev.destAddress ||
+            !ev.eventId
+          ) {
             throw new Error('LockedCanonical missing required fields');
           }
 
-          const eventId = generateEventIdCore({
-            sourceChain: ev.sourceChain,
-            txHash: ev.txHash,
-            logIndex: ev.logIndex,
-            token: ev.token,
-            amount: ev.amount,
-            nonce: ev.nonce,
-            destChainId: ev.destChainId,
-            destAddress: ev.destAddress,
-          });
+          const eventId = ev.eventId;
 
           await persistCasperSourceEvent({
             eventId,
@@ -140,20 +135,16 @@ export function startCasperListener() {
         case 'BurnedWrapped': {
           const ev = normalizeCasperBurnedWrapped(msg);
 
-          if (!ev.nonce || !ev.destChainId || !ev.destAddress) {
+          if (
+            !ev.nonce ||
+            !ev.destChainId ||
+            !ev.destAddress ||
+            !ev.eventId
+          ) {
             throw new Error('BurnedWrapped missing required fields');
           }
 
-          const eventId = generateEventIdCore({
-            sourceChain: ev.sourceChain,
-            txHash: ev.txHash,
-            logIndex: ev.logIndex,
-            token: ev.token,
-            amount: ev.amount,
-            nonce: ev.nonce,
-            destChainId: ev.destChainId,
-            destAddress: ev.destAddress,
-          });
+          const eventId = ev.eventId;
 
           await persistCasperSourceEvent({
             eventId,
@@ -2,20 +2,26 @@ import { BRIDGE_EVENT } from '@prisma/client';
 import { NormalizedBridgeEvent } from '../../../lib/utils/normalizedBridgeEvent';
 import { CasperContractEventWSMessage } from '../types';
 import {
+  normalizeCasperEventId,
   normalizeCasperRecipientBytes32,
   normalizeCasperTxHash,
 } from './utils';
 
 export interface CasperBurnedWrappedPayload {
   token: string;
+  remote_token?: string | number[] | Uint8Array;
   sender: string;
   recipient: string | number[] | Uint8Array;
   amount?: string;
   gross_amount?: string;
   net_amount?: string;
   fee: string;
+  submitter?: string | null;
+  submitter_fee?: string;
   destination_chain: number;
   nonce: number;
+  // Cross-chain id computed by BridgeCore (`compute_outgoing_event_id`)
+  event_id: string | number[] | Uint8Array;
 }
 
 export function normalizeCasperBurnedWrapped(
@@ -51,5 +57,7 @@ export function normalizeCasperBurnedWrapped(
     nonce: payload.nonce.toString(),
     destChainId: payload.destination_chain.toString(),
     destAddress: normalizeCasperRecipientBytes32(payload.recipient),
+
+    eventId: normalizeCasperEventId(payload.event_id),
   };
 }
@@ -3,6 +3,7 @@ import { NormalizedBridgeEvent } from '../../../lib/utils/normalizedBridgeEvent'
 import { CasperContractEventWSMessage } from '../types';
 import { CasperLockedCanonicalPayload } from '../types';
 import {
+  normalizeCasperEventId,
   normalizeCasperRecipientBytes32,
   normalizeCasperTxHash,
 } from './utils';
@@ -56,5 +57,8 @@ export function normalizeCasperLockedCanonical(
     nonce: payload.nonce.toString(),
     destChainId: payload.destination_chain.toString(),
     destAddress: normalizeCasperRecipientBytes32(payload.recipient),
+
+    // Emitted by the contract, so the indexer and destination agree on it
+    eventId: normalizeCasperEventId(payload.event_id),
   };
 }
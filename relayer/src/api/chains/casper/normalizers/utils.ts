@@ -7,6 +7,9 @@ export function normalizeCasperEventId(eventId: unknown): string {
     const hex = Buffer.from(Uint8Array.from(eventId)).toString('hex');
     return `0x${hex}`;
   }
+  if (eventId instanceof Uint8Array) {
+    return `0x${Buffer.from(eventId).toString('hex')}`;
+  }
 
   if (typeof eventId === 'string') {
     const clean = eventId.startsWith('0x') ? eventId : `0x${eventId}`;
@@ -1,6 +1,5 @@
 import { CHAIN } from '@prisma/client';
 import prisma from '../../lib/utils/clients/prisma-client';
-import { generateEventId } from '../../lib/utils/eventId';
 import { logger } from '../../lib/utils/logger';
 import { resolveChainRefId } from '../../lib/utils/chainResolver';
 import { resolveSourceTokenId } from '../../lib/utils/tokenMapping';
@@ -9,16 +8,11 @@ import { NormalizedCasperLockedCanonicalBackfillEvent } from './backFillEvents';
 export async function persistCasperLockedCanonicalEvent(
   ev: NormalizedCasperLockedCanonicalBackfillEvent,
 ) {
-  const eventId = generateEventId({
-    sourceChain: ev.sourceChain,
-    txHash: ev.txHash,
-    logIndex: ev.logIndex,
-    token: ev.token,
-    amount: ev.amount,
-    nonce: ev.nonce,
-    destChainId: ev.destChainId,
-    destAddress: ev.destAddress,
-  });
+  // Emitted by BridgeCore; the same id the destination chain checks
+  const eventId = ev.eventId;
+  if (!eventId) {
+    throw new Error('LockedCanonical missing eventId');
+  }
 
   const destChainIdNum =
     ev.destChainId && Number.isFinite(Number(ev.destChainId))
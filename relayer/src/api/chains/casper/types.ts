@@ -24,12 +24,17 @@ export interface CasperContractEventWSMessage {
 
 export interface CasperLockedCanonicalPayload {
   token: string;
+  remote_token?: string | number[] | Uint8Array;
   sender: string;
   recipient: string | number[] | Uint8Array;
   amount?: string;
   gross_amount?: string;
   net_amount?: string;
   fee: string;
+  submitter?: string | null;
+  submitter_fee?: string;
   destination_chain: number;
   nonce: number;
+  // Cross-chain id computed by BridgeCore (`compute_outgoing_event_id`)
+  event_id: string | number[] | Uint8Array;
 }
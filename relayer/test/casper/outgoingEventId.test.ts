@@ -0,0 +1,36 @@
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+import fixture from '../../../contracts/casper/bridge-core/tests/fixtures/outgoing_events.json';
+import { CasperContractEventWSMessage } from '../../src/api/chains/casper/types';
+import { normalizeCasperLockedCanonical } from '../../src/api/chains/casper/normalizers/normalizeLockedCanonical';
+import { normalizeCasperBurnedWrapped } from '../../src/api/chains/casper/normalizers/normalizeBurnedWrapped';
+
+/**
+ * The fixture is generated from the emitted events by the bridge_core test
+ * outgoing_events_match_relayer_fixture, so these tests pin the indexer to
+ * the exact bytes the contract emits.
+ */
+
+test('LockedCanonical: eventId is the emitted event_id (hex string)', () => {
+  const { message, expectedEventId } = fixture.LockedCanonical;
+
+  const ev = normalizeCasperLockedCanonical(
+    message as CasperContractEventWSMessage,
+  );
+
+  assert.equal(ev.eventId, expectedEventId);
+  assert.equal(ev.nonce, '1');
+  assert.equal(ev.netAmount, '990');
+});
+
+test('BurnedWrapped: eventId is the emitted event_id (byte array)', () => {
+  const { message, expectedEventId } = fixture.BurnedWrapped;
+
+  const ev = normalizeCasperBurnedWrapped(
+    message as CasperContractEventWSMessage,
+  );
+
+  assert.equal(ev.eventId, expectedEventId);
+  assert.equal(ev.nonce, '2');
+  assert.equal(ev.netAmount, '495');
+});